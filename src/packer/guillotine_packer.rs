@@ -0,0 +1,171 @@
+use crate::{
+    frame::Frame,
+    packer::{merge_free_rects, Packer},
+    rect::Rect,
+    texture_packer_config::TexturePackerConfig,
+};
+
+/// A bin-packing allocator based on the guillotine/free-rectangle scheme
+/// commonly used by atlas texture caches. Unlike [`SkylinePacker`](crate::packer::SkylinePacker),
+/// it keeps the whole atlas as a set of free rectangles and is generally
+/// better suited to heterogeneous sprite sizes.
+pub struct GuillotinePacker {
+    config: TexturePackerConfig,
+    border: Rect,
+
+    // candidate free space, in no particular order
+    free_rects: Vec<Rect>,
+}
+
+impl GuillotinePacker {
+    pub fn new(config: TexturePackerConfig) -> Self {
+        let border = Rect::new(0, 0, config.max_width, config.max_height);
+
+        GuillotinePacker {
+            config,
+            border,
+            free_rects: vec![border],
+        }
+    }
+
+    // Best Area Fit: among the free rects that can hold a `w`x`h` rect
+    // (trying the rotated dimensions too, if allowed), pick the one that
+    // wastes the least area. Returns the free rect's index along with the
+    // dimensions actually used and whether they were rotated.
+    fn find_best_fit(&self, w: u32, h: u32) -> Option<(usize, u32, u32, bool)> {
+        let mut best: Option<(usize, u32, u32, bool, u64)> = None;
+
+        for (i, free) in self.free_rects.iter().enumerate() {
+            if free.w >= w && free.h >= h {
+                let waste = free.w as u64 * free.h as u64 - w as u64 * h as u64;
+                if best.map_or(true, |(.., best_waste)| waste < best_waste) {
+                    best = Some((i, w, h, false, waste));
+                }
+            }
+
+            if self.config.allow_rotation && free.w >= h && free.h >= w {
+                let waste = free.w as u64 * free.h as u64 - w as u64 * h as u64;
+                if best.map_or(true, |(.., best_waste)| waste < best_waste) {
+                    best = Some((i, h, w, true, waste));
+                }
+            }
+        }
+
+        best.map(|(i, w, h, rotated, _)| (i, w, h, rotated))
+    }
+
+    // Shorter-Axis-Split: having placed a `w`x`h` rect at the top-left of
+    // `free`, split the remaining L-shape into two non-overlapping child
+    // free rects and push the non-empty ones back.
+    fn split(&mut self, index: usize, w: u32, h: u32) {
+        let free = self.free_rects.remove(index);
+
+        let right_w = free.w - w;
+        let bottom_h = free.h - h;
+
+        let (right, bottom) = if right_w < bottom_h {
+            (
+                Rect::new(free.x + w, free.y, right_w, h),
+                Rect::new(free.x, free.y + h, free.w, bottom_h),
+            )
+        } else {
+            (
+                Rect::new(free.x + w, free.y, right_w, free.h),
+                Rect::new(free.x, free.y + h, w, bottom_h),
+            )
+        };
+
+        if right.w > 0 && right.h > 0 {
+            self.free_rects.push(right);
+        }
+        if bottom.w > 0 && bottom.h > 0 {
+            self.free_rects.push(bottom);
+        }
+    }
+}
+
+impl<K> Packer<K> for GuillotinePacker {
+    fn pack(&mut self, key: K, texture_rect: &Rect) -> Option<Frame<K>> {
+        let width = texture_rect.w + self.config.texture_padding + self.config.texture_extrusion * 2;
+        let height = texture_rect.h + self.config.texture_padding + self.config.texture_extrusion * 2;
+
+        let (index, w, h, rotated) = self.find_best_fit(width, height)?;
+        let free = self.free_rects[index].clone();
+        self.split(index, w, h);
+        merge_free_rects(&mut self.free_rects);
+
+        let frame = Rect::new(
+            free.x,
+            free.y,
+            w - self.config.texture_padding - self.config.texture_extrusion * 2,
+            h - self.config.texture_padding - self.config.texture_extrusion * 2,
+        );
+
+        Some(Frame {
+            key,
+            frame,
+            rotated,
+            trimmed: false,
+            source: Rect {
+                x: 0,
+                y: 0,
+                w: texture_rect.w,
+                h: texture_rect.h,
+            },
+        })
+    }
+
+    fn can_pack(&self, texture_rect: &Rect) -> bool {
+        let width = texture_rect.w + self.config.texture_padding + self.config.texture_extrusion * 2;
+        let height = texture_rect.h + self.config.texture_padding + self.config.texture_extrusion * 2;
+
+        self.find_best_fit(width, height).is_some()
+    }
+
+    fn border(&self) -> Rect {
+        self.border.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn config(max_width: u32, max_height: u32) -> TexturePackerConfig {
+        TexturePackerConfig {
+            max_width,
+            max_height,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn shorter_axis_split_prefers_the_taller_leftover() {
+        // 10x10 atlas, place a 4x2 rect: leftover width (6) < leftover
+        // height (8), so the split is horizontal and the right-of-item
+        // strip only spans the item's own height.
+        let mut packer = GuillotinePacker::new(config(10, 10));
+
+        let frame = packer.pack("a", &Rect::new(0, 0, 4, 2)).unwrap();
+        assert_eq!(frame.frame, Rect::new(0, 0, 4, 2));
+
+        assert_eq!(packer.free_rects.len(), 2);
+        assert!(packer.free_rects.contains(&Rect::new(4, 0, 6, 2)));
+        assert!(packer.free_rects.contains(&Rect::new(0, 2, 10, 8)));
+    }
+
+    #[test]
+    fn best_area_fit_picks_the_tightest_free_rect_over_a_larger_one() {
+        let mut packer = GuillotinePacker::new(config(20, 20));
+
+        // leaves free rects (10,0,10,2) and (0,2,20,18)
+        packer.pack("a", &Rect::new(0, 0, 10, 2)).unwrap();
+        // an 8x2 item in the 10x2 free rect leaves a 2x2 sliver alongside
+        // the much larger (0,2,20,18) free rect
+        packer.pack("b", &Rect::new(0, 0, 8, 2)).unwrap();
+
+        // a 2x2 item should land in that tight sliver, not the big leftover
+        let frame = packer.pack("c", &Rect::new(0, 0, 2, 2)).unwrap();
+        assert_eq!(frame.frame, Rect::new(18, 0, 2, 2));
+    }
+}
@@ -0,0 +1,115 @@
+//! Placement strategies implementing [`Packer`].
+mod guillotine_packer;
+mod multi_page_packer;
+mod shelf_packer;
+mod skyline_packer;
+
+pub use guillotine_packer::GuillotinePacker;
+pub use multi_page_packer::{MultiPagePacker, PagedFrame};
+pub use shelf_packer::ShelfPacker;
+pub use skyline_packer::SkylinePacker;
+
+use crate::{frame::Frame, rect::Rect};
+
+/// A placement strategy that arranges rects inside a bounded atlas.
+pub trait Packer<K> {
+    /// Reserve space for `texture_rect` and return its placement, or `None`
+    /// if it doesn't fit.
+    fn pack(&mut self, key: K, texture_rect: &Rect) -> Option<Frame<K>>;
+
+    /// Whether `texture_rect` would fit, without actually placing it.
+    fn can_pack(&self, texture_rect: &Rect) -> bool;
+
+    /// The packable area, used by the default `frame_center_before_trimming`
+    /// to clamp centers that fall outside it.
+    fn border(&self) -> Rect;
+
+    /// Evict a previously packed rect so its space can be reused.
+    ///
+    /// Eviction is currently [`SkylinePacker`]-only: it's the only
+    /// implementation that tracks placements by key, so every other packer
+    /// leaves this a no-op rather than pretending to support it.
+    fn remove(&mut self, _key: &K) {}
+
+    /// Forget every placement, as if the packer were newly constructed.
+    /// See [`Packer::remove`] for which packers actually track placements.
+    fn clear(&mut self) {}
+
+    /// The center of `frame` before trimming was applied, or its own center
+    /// if it was never trimmed.
+    fn frame_center_before_trimming(&self, frame: Frame<K>) -> (u32, u32) {
+        // if not trimmed, just return the frame center:
+        if !frame.trimmed {
+            let cx = frame.frame.x + frame.frame.w / 2;
+            let cy = frame.frame.y + frame.frame.h / 2;
+            return (cx, cy);
+        }
+
+        // size of x and y trimming in pixels:
+        let trim_x = frame.source.x;
+        let trim_y = frame.source.y;
+
+        // move back the frame position by trimming amount; trim and frame
+        // coordinates live in different spaces, so this can't assume
+        // trim_x/trim_y are smaller than the frame position:
+        let og_start_x = frame.frame.x.saturating_sub(trim_x);
+        let og_start_y = frame.frame.y.saturating_sub(trim_y);
+
+        // original width and height without trimming:
+        let og_start_w = frame.source.w;
+        let og_start_h = frame.source.h;
+
+        // calculate original center:
+        let center_x = og_start_x + og_start_w / 2;
+        let center_y = og_start_y + og_start_h / 2;
+
+        // if we are outside the packer's dimensions, clamp to its border:
+        let border = self.border();
+        let clamp_x = center_x.clamp(border.x, border.w);
+        let clamp_y = center_y.clamp(border.y, border.h);
+
+        (clamp_x, clamp_y)
+    }
+}
+
+// Coalesce any two rects in `free_rects` that share a full edge into one.
+// Shared by every packer that maintains a free-rect list (guillotine- and
+// skyline-style reuse alike), so the coalescing logic can't drift between
+// them.
+pub(crate) fn merge_free_rects(free_rects: &mut Vec<Rect>) {
+    let mut i = 0;
+    while i < free_rects.len() {
+        let mut merged = None;
+
+        for j in 0..free_rects.len() {
+            if i == j {
+                continue;
+            }
+
+            let a = &free_rects[i];
+            let b = &free_rects[j];
+
+            let joined = if a.y == b.y && a.h == b.h && a.x + a.w == b.x {
+                Some(Rect::new(a.x, a.y, a.w + b.w, a.h))
+            } else if a.x == b.x && a.w == b.w && a.y + a.h == b.y {
+                Some(Rect::new(a.x, a.y, a.w, a.h + b.h))
+            } else {
+                None
+            };
+
+            if let Some(joined) = joined {
+                merged = Some((j, joined));
+                break;
+            }
+        }
+
+        if let Some((j, joined)) = merged {
+            let (lo, hi) = if i < j { (i, j) } else { (j, i) };
+            free_rects.remove(hi);
+            free_rects.remove(lo);
+            free_rects.push(joined);
+        } else {
+            i += 1;
+        }
+    }
+}
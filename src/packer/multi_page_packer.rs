@@ -0,0 +1,81 @@
+use crate::{frame::Frame, packer::Packer, rect::Rect, texture_packer_config::TexturePackerConfig};
+
+/// A frame placed by a [`MultiPagePacker`], carrying the index of the page
+/// it landed on alongside the usual frame.
+#[derive(Clone, Debug)]
+pub struct PagedFrame<K> {
+    pub page: usize,
+    pub frame: Frame<K>,
+}
+
+/// Packs into a growing sequence of same-sized pages instead of a single
+/// atlas, for sprite sets too large to fit `max_width`x`max_height`.
+///
+/// Each page is built with the `new_page` constructor passed to [`MultiPagePacker::new`],
+/// so any `Packer<K>` implementation (e.g. [`SkylinePacker`](crate::packer::SkylinePacker))
+/// can be used as the per-page strategy.
+pub struct MultiPagePacker<K, P: Packer<K>> {
+    config: TexturePackerConfig,
+    new_page: Box<dyn Fn(TexturePackerConfig) -> P>,
+    pages: Vec<P>,
+    _key: std::marker::PhantomData<K>,
+}
+
+impl<K, P: Packer<K>> MultiPagePacker<K, P> {
+    pub fn new(config: TexturePackerConfig, new_page: impl Fn(TexturePackerConfig) -> P + 'static) -> Self {
+        MultiPagePacker {
+            config,
+            new_page: Box::new(new_page),
+            pages: Vec::new(),
+            _key: std::marker::PhantomData,
+        }
+    }
+
+    /// Number of pages created so far.
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// The packer for a single page, or `None` if there is no such page.
+    /// Pages don't record the frames placed on them; each frame is only
+    /// returned, with its page index, by [`MultiPagePacker::pack`].
+    pub fn page(&self, index: usize) -> Option<&P> {
+        self.pages.get(index)
+    }
+
+    /// The packer for every page, in the order they were created.
+    pub fn pages(&self) -> impl Iterator<Item = &P> {
+        self.pages.iter()
+    }
+
+    pub fn can_pack(&self, texture_rect: &Rect) -> bool {
+        // a fresh page can always be appended, so this only fails if the
+        // rect wouldn't fit a brand-new, empty page
+        self.pages.iter().any(|page| page.can_pack(texture_rect))
+            || (self.new_page)(self.config.clone()).can_pack(texture_rect)
+    }
+
+    /// Pack `key`/`texture_rect` into the first page with room, appending a
+    /// fresh page if none of the existing ones fit.
+    pub fn pack(&mut self, key: K, texture_rect: &Rect) -> Option<PagedFrame<K>>
+    where
+        K: Clone,
+    {
+        for (page, packer) in self.pages.iter_mut().enumerate() {
+            if packer.can_pack(texture_rect) {
+                if let Some(frame) = packer.pack(key.clone(), texture_rect) {
+                    return Some(PagedFrame { page, frame });
+                }
+            }
+        }
+
+        let mut packer = (self.new_page)(self.config.clone());
+        let frame = packer.pack(key, texture_rect)?;
+        self.pages.push(packer);
+
+        Some(PagedFrame {
+            page: self.pages.len() - 1,
+            frame,
+        })
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{frame::Frame, packer::Packer, rect::Rect, texture_packer_config::TexturePackerConfig};
+
+struct Shelf {
+    y: u32,
+    height: u32,
+    cursor_x: u32,
+}
+
+/// A row (shelf) allocator, well suited to packing many items of similar
+/// height such as font glyphs or tile rows, a case [`SkylinePacker`](crate::packer::SkylinePacker)
+/// handles poorly.
+pub struct ShelfPacker {
+    config: TexturePackerConfig,
+    border: Rect,
+
+    // shelves in the order they were opened, bottom of the last one tracked
+    // separately so opening a new shelf doesn't need to scan them
+    shelves: Vec<Shelf>,
+    prev_bottom: u32,
+}
+
+impl ShelfPacker {
+    pub fn new(config: TexturePackerConfig) -> Self {
+        ShelfPacker {
+            border: Rect::new(0, 0, config.max_width, config.max_height),
+            config,
+            shelves: Vec::new(),
+            prev_bottom: 0,
+        }
+    }
+
+    // Find the shelf that wastes the least height while fitting a `w`x`h`
+    // rect, trying the rotated dimensions too when that wastes less.
+    // Returns the shelf index and the dimensions actually used.
+    fn find_shelf(&self, w: u32, h: u32) -> Option<(usize, u32, u32, bool)> {
+        let mut best: Option<(usize, u32, u32, bool, u32)> = None;
+
+        for (i, shelf) in self.shelves.iter().enumerate() {
+            if shelf.height >= h && shelf.cursor_x + w <= self.config.max_width {
+                let waste = shelf.height - h;
+                if best.map_or(true, |(.., best_waste)| waste < best_waste) {
+                    best = Some((i, w, h, false, waste));
+                }
+            }
+
+            if self.config.allow_rotation
+                && shelf.height >= w
+                && shelf.cursor_x + h <= self.config.max_width
+            {
+                let waste = shelf.height - w;
+                if best.map_or(true, |(.., best_waste)| waste < best_waste) {
+                    best = Some((i, h, w, true, waste));
+                }
+            }
+        }
+
+        best.map(|(i, w, h, rotated, _)| (i, w, h, rotated))
+    }
+}
+
+impl<K> Packer<K> for ShelfPacker {
+    fn pack(&mut self, key: K, texture_rect: &Rect) -> Option<Frame<K>> {
+        let width = texture_rect.w + self.config.texture_padding + self.config.texture_extrusion * 2;
+        let height = texture_rect.h + self.config.texture_padding + self.config.texture_extrusion * 2;
+
+        let (index, w, h, rotated) = match self.find_shelf(width, height) {
+            Some(found) => found,
+            None => {
+                if self.prev_bottom + height > self.config.max_height || width > self.config.max_width
+                {
+                    return None;
+                }
+
+                self.shelves.push(Shelf {
+                    y: self.prev_bottom,
+                    height,
+                    cursor_x: 0,
+                });
+                self.prev_bottom += height;
+
+                (self.shelves.len() - 1, width, height, false)
+            }
+        };
+
+        let shelf = &mut self.shelves[index];
+        let x = shelf.cursor_x;
+        let y = shelf.y;
+        shelf.cursor_x += w;
+
+        let frame = Rect::new(
+            x,
+            y,
+            w - self.config.texture_padding - self.config.texture_extrusion * 2,
+            h - self.config.texture_padding - self.config.texture_extrusion * 2,
+        );
+
+        Some(Frame {
+            key,
+            frame,
+            rotated,
+            trimmed: false,
+            source: Rect {
+                x: 0,
+                y: 0,
+                w: texture_rect.w,
+                h: texture_rect.h,
+            },
+        })
+    }
+
+    fn can_pack(&self, texture_rect: &Rect) -> bool {
+        let width = texture_rect.w + self.config.texture_padding + self.config.texture_extrusion * 2;
+        let height = texture_rect.h + self.config.texture_padding + self.config.texture_extrusion * 2;
+
+        if self.find_shelf(width, height).is_some() {
+            return true;
+        }
+
+        width <= self.config.max_width && self.prev_bottom + height <= self.config.max_height
+    }
+
+    fn border(&self) -> Rect {
+        self.border.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn config(max_width: u32, max_height: u32) -> TexturePackerConfig {
+        TexturePackerConfig {
+            max_width,
+            max_height,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reuses_the_same_shelf_for_same_height_items() {
+        let mut packer = ShelfPacker::new(config(100, 100));
+
+        let a = packer.pack("a", &Rect::new(0, 0, 10, 8)).unwrap();
+        let b = packer.pack("b", &Rect::new(0, 0, 12, 8)).unwrap();
+
+        assert_eq!(a.frame, Rect::new(0, 0, 10, 8));
+        assert_eq!(b.frame, Rect::new(10, 0, 12, 8));
+        assert_eq!(packer.shelves.len(), 1);
+    }
+
+    #[test]
+    fn opens_a_new_shelf_when_height_does_not_fit() {
+        let mut packer = ShelfPacker::new(config(100, 100));
+
+        packer.pack("a", &Rect::new(0, 0, 10, 8)).unwrap();
+        let b = packer.pack("b", &Rect::new(0, 0, 10, 20)).unwrap();
+
+        // "b" is taller than the open shelf, so it starts a fresh one below
+        assert_eq!(b.frame, Rect::new(0, 8, 10, 20));
+        assert_eq!(packer.shelves.len(), 2);
+    }
+
+    #[test]
+    fn fails_once_the_atlas_height_is_exhausted() {
+        let mut packer = ShelfPacker::new(config(10, 10));
+
+        assert!(packer.pack("a", &Rect::new(0, 0, 10, 8)).is_some());
+        assert!(packer.pack("b", &Rect::new(0, 0, 10, 8)).is_none());
+    }
+}
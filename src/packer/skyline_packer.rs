@@ -1,5 +1,13 @@
-use crate::{frame::Frame, packer::Packer, rect::Rect, texture_packer_config::TexturePackerConfig};
+use crate::{
+    frame::Frame,
+    packer::{merge_free_rects, Packer},
+    rect::Rect,
+    texture::Texture,
+    texture_packer_config::TexturePackerConfig,
+};
 use std::cmp::max;
+use std::collections::HashMap;
+use std::hash::Hash;
 
 struct Skyline {
     pub x: u32,
@@ -19,15 +27,22 @@ impl Skyline {
     }
 }
 
-pub struct SkylinePacker {
+pub struct SkylinePacker<K> {
     config: TexturePackerConfig,
     border: Rect,
 
     // the skylines are sorted by their `x` position
     skylines: Vec<Skyline>,
+
+    // rects reclaimed by `remove`, reused with first-fit before the skyline
+    // is extended
+    free_rects: Vec<Rect>,
+
+    // every rect currently allocated, so `remove` can find it back by key
+    placements: HashMap<K, Rect>,
 }
 
-impl SkylinePacker {
+impl<K: Eq + Hash> SkylinePacker<K> {
     pub fn new(config: TexturePackerConfig) -> Self {
         let skylines = vec![Skyline {
             x: 0,
@@ -39,9 +54,46 @@ impl SkylinePacker {
             config,
             border: Rect::new(0, 0, config.max_width, config.max_height),
             skylines,
+            free_rects: Vec::new(),
+            placements: HashMap::new(),
         }
     }
 
+    // First-fit: the first free rect that can hold a `w`x`h` rect (trying
+    // the rotated dimensions too, if allowed). Returns the free rect's
+    // index along with the dimensions actually used and whether rotated.
+    fn find_free_rect(&self, w: u32, h: u32) -> Option<(usize, u32, u32, bool)> {
+        for (i, free) in self.free_rects.iter().enumerate() {
+            if free.w >= w && free.h >= h {
+                return Some((i, w, h, false));
+            }
+
+            if self.config.allow_rotation && free.w >= h && free.h >= w {
+                return Some((i, h, w, true));
+            }
+        }
+
+        None
+    }
+
+    // Place a `w`x`h` rect at the top-left of the free rect at `index`,
+    // splitting the remainder back into the free list.
+    fn use_free_rect(&mut self, index: usize, w: u32, h: u32) -> Rect {
+        let free = self.free_rects.remove(index);
+
+        let right = Rect::new(free.x + w, free.y, free.w - w, h);
+        let bottom = Rect::new(free.x, free.y + h, free.w, free.h - h);
+
+        if right.w > 0 && right.h > 0 {
+            self.free_rects.push(right);
+        }
+        if bottom.w > 0 && bottom.h > 0 {
+            self.free_rects.push(bottom);
+        }
+
+        Rect::new(free.x, free.y, w, h)
+    }
+
     // return `rect` if rectangle (w, h) can fit the skyline started at `i`
     fn can_put(&self, mut i: usize, w: u32, h: u32) -> Option<Rect> {
         let mut rect = Rect::new(self.skylines[i].x, 0, w, h);
@@ -137,45 +189,121 @@ impl SkylinePacker {
     }
 }
 
-impl<K> Packer<K> for SkylinePacker {
-    fn pack(&mut self, key: K, texture_rect: &Rect) -> Option<Frame<K>> {
-        let mut width = texture_rect.w;
-        let mut height = texture_rect.h;
+impl<K: Eq + Hash + Clone> SkylinePacker<K> {
+    /// Pack a texture directly, honoring `config.trim`: when enabled, the
+    /// texture is scanned for the tight bounding box of texels whose alpha
+    /// (as returned by `alpha`) is above `config.texture_trimmed_alpha_threshold`,
+    /// only that sub-rect is packed, and `Frame::source`/`Frame::trimmed`
+    /// are filled in with the trim offset and the texture's original size.
+    pub fn pack_texture<T: Texture>(
+        &mut self,
+        key: K,
+        texture: &T,
+        alpha: impl Fn(&T::Pixel) -> u8,
+    ) -> Option<Frame<K>> {
+        let width = texture.width();
+        let height = texture.height();
+
+        let trimmed_rect = if self.config.trim {
+            trim_bounds(texture, &alpha, self.config.texture_trimmed_alpha_threshold)
+                .unwrap_or_else(|| Rect::new(0, 0, width, height))
+        } else {
+            Rect::new(0, 0, width, height)
+        };
 
-        width += self.config.texture_padding + self.config.texture_extrusion * 2;
-        height += self.config.texture_padding + self.config.texture_extrusion * 2;
+        let mut frame = self.pack(
+            key,
+            &Rect::new(0, 0, trimmed_rect.w, trimmed_rect.h),
+        )?;
 
-        if let Some((i, mut rect)) = self.find_skyline(width, height) {
+        frame.trimmed = trimmed_rect.w != width || trimmed_rect.h != height;
+        frame.source = Rect::new(trimmed_rect.x, trimmed_rect.y, width, height);
+
+        Some(frame)
+    }
+}
+
+// Scan every texel for the tight bounding box of pixels whose alpha is above
+// `threshold`. Returns `None` if the texture is fully transparent.
+fn trim_bounds<T: Texture>(
+    texture: &T,
+    alpha: &impl Fn(&T::Pixel) -> u8,
+    threshold: u8,
+) -> Option<Rect> {
+    let width = texture.width();
+    let height = texture.height();
+
+    let (mut min_x, mut min_y) = (width, height);
+    let (mut max_x, mut max_y) = (0, 0);
+    let mut found = false;
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(pixel) = texture.get(x, y) {
+                if alpha(&pixel) > threshold {
+                    min_x = min_x.min(x);
+                    min_y = min_y.min(y);
+                    max_x = max_x.max(x);
+                    max_y = max_y.max(y);
+                    found = true;
+                }
+            }
+        }
+    }
+
+    if !found {
+        return None;
+    }
+
+    Some(Rect::new(min_x, min_y, max_x - min_x + 1, max_y - min_y + 1))
+}
+
+impl<K: Eq + Hash + Clone> Packer<K> for SkylinePacker<K> {
+    fn pack(&mut self, key: K, texture_rect: &Rect) -> Option<Frame<K>> {
+        let width = texture_rect.w + self.config.texture_padding + self.config.texture_extrusion * 2;
+        let height = texture_rect.h + self.config.texture_padding + self.config.texture_extrusion * 2;
+
+        let (mut rect, rotated) = if let Some((i, w, h, rotated)) = self.find_free_rect(width, height) {
+            let rect = self.use_free_rect(i, w, h);
+            merge_free_rects(&mut self.free_rects);
+            (rect, rotated)
+        } else if let Some((i, rect)) = self.find_skyline(width, height) {
             self.split(i, &rect);
             self.merge();
-
             let rotated = width != rect.w;
-
-            rect.w -= self.config.texture_padding + self.config.texture_extrusion * 2;
-            rect.h -= self.config.texture_padding + self.config.texture_extrusion * 2;
-
-            Some(Frame {
-                key,
-                frame: rect,
-                rotated,
-                trimmed: false,
-                source: Rect {
-                    x: 0,
-                    y: 0,
-                    w: texture_rect.w,
-                    h: texture_rect.h,
-                },
-            })
+            (rect, rotated)
         } else {
-            None
-        }
+            return None;
+        };
+
+        self.placements.insert(key.clone(), rect.clone());
+
+        rect.w -= self.config.texture_padding + self.config.texture_extrusion * 2;
+        rect.h -= self.config.texture_padding + self.config.texture_extrusion * 2;
+
+        Some(Frame {
+            key,
+            frame: rect,
+            rotated,
+            trimmed: false,
+            source: Rect {
+                x: 0,
+                y: 0,
+                w: texture_rect.w,
+                h: texture_rect.h,
+            },
+        })
     }
 
     fn can_pack(&self, texture_rect: &Rect) -> bool {
-        if let Some((_, rect)) = self.find_skyline(
-            texture_rect.w + self.config.texture_padding + self.config.texture_extrusion * 2,
-            texture_rect.h + self.config.texture_padding + self.config.texture_extrusion * 2,
-        ) {
+        let width = texture_rect.w + self.config.texture_padding + self.config.texture_extrusion * 2;
+        let height = texture_rect.h + self.config.texture_padding + self.config.texture_extrusion * 2;
+
+        if self.find_free_rect(width, height).is_some() {
+            return true;
+        }
+
+        if let Some((_, rect)) = self.find_skyline(width, height) {
             let skyline = Skyline {
                 x: rect.left(),
                 y: rect.bottom() + 1,
@@ -187,35 +315,147 @@ impl<K> Packer<K> for SkylinePacker {
         false
     }
 
-    fn frame_center_before_trimming(&self, frame: Frame<K>) -> (u32, u32) {
+    fn border(&self) -> Rect {
+        self.border.clone()
+    }
+
+    fn remove(&mut self, key: &K) {
+        if let Some(rect) = self.placements.remove(key) {
+            self.free_rects.push(rect);
+            merge_free_rects(&mut self.free_rects);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.skylines = vec![Skyline {
+            x: 0,
+            y: 0,
+            w: self.config.max_width,
+        }];
+        self.free_rects.clear();
+        self.placements.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn config(max_width: u32, max_height: u32) -> TexturePackerConfig {
+        TexturePackerConfig {
+            max_width,
+            max_height,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn remove_then_pack_reuses_the_freed_rect() {
+        let mut packer: SkylinePacker<&str> = SkylinePacker::new(config(100, 100));
+
+        let a = packer.pack("a", &Rect::new(0, 0, 20, 20)).unwrap();
+        assert_eq!(a.frame, Rect::new(0, 0, 20, 20));
+
+        packer.remove(&"a");
+        assert_eq!(packer.free_rects.len(), 1);
+
+        // a same-sized item should land exactly where "a" was, via the
+        // free list, instead of extending the skyline further down
+        let b = packer.pack("b", &Rect::new(0, 0, 20, 20)).unwrap();
+        assert_eq!(b.frame, Rect::new(0, 0, 20, 20));
+        assert!(packer.free_rects.is_empty());
+    }
+
+    #[test]
+    fn clear_forgets_every_placement() {
+        let mut packer: SkylinePacker<&str> = SkylinePacker::new(config(100, 100));
 
-        // if not trimmed, just return the frame center:
-        if !frame.trimmed {
-            let cx = frame.frame.x + frame.frame.w / 2;
-            let cy = frame.frame.y + frame.frame.h / 2;
-            return (cx, cy)
+        packer.pack("a", &Rect::new(0, 0, 20, 20)).unwrap();
+        packer.clear();
+
+        assert!(packer.placements.is_empty());
+        assert!(packer.free_rects.is_empty());
+        assert_eq!(packer.skylines.len(), 1);
+
+        let a = packer.pack("a", &Rect::new(0, 0, 20, 20)).unwrap();
+        assert_eq!(a.frame, Rect::new(0, 0, 20, 20));
+    }
+
+    // A texture backed by a flat buffer of alpha values, for exercising
+    // `trim_bounds` without pulling in a real image type.
+    struct AlphaTexture {
+        width: u32,
+        height: u32,
+        alpha: Vec<u8>,
+    }
+
+    impl Texture for AlphaTexture {
+        type Pixel = u8;
+
+        fn width(&self) -> u32 {
+            self.width
+        }
+
+        fn height(&self) -> u32 {
+            self.height
+        }
+
+        fn get(&self, x: u32, y: u32) -> Option<u8> {
+            self.alpha.get((y * self.width + x) as usize).copied()
+        }
+
+        fn set(&mut self, x: u32, y: u32, val: u8) {
+            self.alpha[(y * self.width + x) as usize] = val;
         }
+    }
+
+    #[test]
+    fn trim_bounds_finds_the_tight_box_around_a_bordered_sprite() {
+        // 5x5 texture, fully transparent except for a 2x1 sprite at (1, 2)
+        let mut alpha = vec![0u8; 25];
+        alpha[2 * 5 + 1] = 255;
+        alpha[2 * 5 + 2] = 255;
+        let texture = AlphaTexture {
+            width: 5,
+            height: 5,
+            alpha,
+        };
 
-        // size of x and y trimming in pixels:
-        let trim_x = frame.source.x;
-        let trim_y = frame.source.y;
+        let bounds = trim_bounds(&texture, &|px: &u8| *px, 0).unwrap();
+        assert_eq!(bounds, Rect::new(1, 2, 2, 1));
+    }
 
-        // move back the frame position by trimming amount:
-        let og_start_x = frame.frame.x - trim_x;
-        let og_start_y = frame.frame.y - trim_y;
+    #[test]
+    fn trim_bounds_is_none_for_a_fully_transparent_texture() {
+        let texture = AlphaTexture {
+            width: 4,
+            height: 4,
+            alpha: vec![0u8; 16],
+        };
 
-        // original width and height without trimming:
-        let og_start_w = frame.source.w;
-        let og_start_h = frame.source.h;
+        assert!(trim_bounds(&texture, &|px: &u8| *px, 0).is_none());
+    }
 
-        // calculate original center:
-        let center_x = og_start_x + og_start_w / 2;
-        let center_y = og_start_y + og_start_h / 2;
+    #[test]
+    fn pack_texture_trims_and_records_the_source_offset() {
+        let mut packer: SkylinePacker<&str> = SkylinePacker::new(config(100, 100));
+        packer.config.trim = true;
+
+        let mut alpha = vec![0u8; 25];
+        alpha[2 * 5 + 1] = 255;
+        alpha[2 * 5 + 2] = 255;
+        let texture = AlphaTexture {
+            width: 5,
+            height: 5,
+            alpha,
+        };
 
-        // if we are outside the packer's dimensions, clamp to its border:
-        let clamp_x = center_x.clamp(self.border.x, self.border.w);
-        let clamp_y = center_y.clamp(self.border.y, self.border.h);
+        let frame = packer
+            .pack_texture("a", &texture, |px: &u8| *px)
+            .unwrap();
 
-        (clamp_x, clamp_y)
+        assert!(frame.trimmed);
+        assert_eq!(frame.frame, Rect::new(0, 0, 2, 1));
+        assert_eq!(frame.source, Rect::new(1, 2, 5, 5));
     }
 }